@@ -0,0 +1,16 @@
+use soroban_auth::Signature;
+use soroban_sdk::Env;
+
+use crate::error::Error;
+use crate::storage::{has_admin, is_renounced, read_admin};
+
+pub fn check_admin(e: &Env, auth: &Signature) -> Result<(), Error> {
+    if !has_admin(e) {
+        return Err(Error::NotInitialized);
+    }
+    // once renounced no one is the admin, whatever signature they present.
+    if is_renounced(e) || auth.identifier(e) != read_admin(e) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
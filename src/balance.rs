@@ -0,0 +1,51 @@
+use soroban_auth::Identifier;
+use soroban_sdk::{BigInt, Env, Map};
+
+use crate::error::Error;
+use crate::storage::DataKey;
+
+fn read_balances(e: &Env) -> Map<Identifier, BigInt> {
+    e.data()
+        .get(DataKey::Balances)
+        .unwrap_or_else(|| Ok(Map::new(e)))
+        .unwrap()
+}
+
+pub fn read_balance(e: &Env, id: &Identifier) -> BigInt {
+    read_balances(e)
+        .get(id.clone())
+        .unwrap_or_else(|| Ok(BigInt::zero(e)))
+        .unwrap()
+}
+
+fn write_balance(e: &Env, id: &Identifier, amount: BigInt) {
+    let mut balances = read_balances(e);
+    balances.set(id.clone(), amount);
+    e.data().set(DataKey::Balances, balances);
+}
+
+pub fn sum_balances(e: &Env) -> BigInt {
+    let mut sum = BigInt::zero(e);
+    for balance in read_balances(e).values().iter() {
+        sum += balance.unwrap();
+    }
+    sum
+}
+
+pub fn remove_balances(e: &Env) {
+    e.data().remove(DataKey::Balances);
+}
+
+pub fn receive_balance(e: &Env, id: &Identifier, amount: &BigInt) {
+    let balance = read_balance(e, id);
+    write_balance(e, id, balance + amount.clone());
+}
+
+pub fn spend_balance(e: &Env, id: &Identifier, amount: &BigInt) -> Result<(), Error> {
+    let balance = read_balance(e, id);
+    if balance < *amount {
+        return Err(Error::InsufficientBalance);
+    }
+    write_balance(e, id, balance - amount.clone());
+    Ok(())
+}
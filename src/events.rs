@@ -0,0 +1,224 @@
+use soroban_auth::Identifier;
+use soroban_sdk::{
+    contracttype, serde::Serialize, symbol, BigInt, Env, IntoVal, RawVal, Symbol, Vec,
+};
+
+use crate::storage::{
+    is_verbose, read_event_seq, read_namespace, read_recent, write_event_seq, write_recent,
+};
+
+/// Number of events kept by `record_recent`.
+pub const RECENT_EVENTS: u32 = 8;
+
+/// Data published by `init`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InitEvent {
+    pub admin: Identifier,
+    pub version: u32,
+    pub timestamp: u64,
+    pub start: u32,
+}
+
+/// Data published by `link`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LinkEvent {
+    pub a: Identifier,
+    pub b: Identifier,
+    pub ledger: u32,
+}
+
+/// Contract state published by `snapshot`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct State {
+    pub admin: Identifier,
+    pub count: u32,
+    pub total: BigInt,
+}
+
+/// First topic of every event published by the contract.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Topic {
+    Init,
+    Admin,
+    Count,
+    Custom,
+    Paused,
+    Unpaused,
+    Batch,
+    Set,
+    Role,
+    Transfer,
+    Mint,
+    Reset,
+    Dead,
+    Deposit,
+    Withdraw,
+    Changed,
+    Link,
+    Migrate,
+    Ttl,
+    Metric,
+    Whoami,
+    Verbose,
+    Failed,
+    Snapshot,
+    Log,
+    Scheduled,
+    Executed,
+    Cap,
+    Map,
+    Supply,
+    Beat,
+}
+
+impl Topic {
+    /// Returns the symbol the topic is published as.
+    pub fn symbol(&self) -> Symbol {
+        match self {
+            Topic::Init => symbol!("init"),
+            Topic::Admin => symbol!("admin"),
+            Topic::Count => symbol!("count"),
+            Topic::Custom => symbol!("custom"),
+            Topic::Paused => symbol!("paused"),
+            Topic::Unpaused => symbol!("unpaused"),
+            Topic::Batch => symbol!("batch"),
+            Topic::Set => symbol!("set"),
+            Topic::Role => symbol!("role"),
+            Topic::Transfer => symbol!("transfer"),
+            Topic::Mint => symbol!("mint"),
+            Topic::Reset => symbol!("reset"),
+            Topic::Dead => symbol!("dead"),
+            Topic::Deposit => symbol!("deposit"),
+            Topic::Withdraw => symbol!("withdraw"),
+            Topic::Changed => symbol!("changed"),
+            Topic::Link => symbol!("link"),
+            Topic::Migrate => symbol!("migrate"),
+            Topic::Ttl => symbol!("ttl"),
+            Topic::Metric => symbol!("metric"),
+            Topic::Whoami => symbol!("whoami"),
+            Topic::Verbose => symbol!("verbose"),
+            Topic::Failed => symbol!("error"),
+            Topic::Snapshot => symbol!("snapshot"),
+            Topic::Log => symbol!("log"),
+            Topic::Scheduled => symbol!("scheduled"),
+            Topic::Executed => symbol!("executed"),
+            Topic::Cap => symbol!("cap"),
+            Topic::Map => symbol!("map"),
+            Topic::Supply => symbol!("supply"),
+            Topic::Beat => symbol!("beat"),
+        }
+    }
+
+    /// Returns whether the topic is only published while the contract is
+    /// verbose.
+    pub fn is_chatty(&self) -> bool {
+        matches!(self, Topic::Count | Topic::Metric)
+    }
+}
+
+/// Returns the symbol published alongside `init` for the kind of `id`.
+pub fn identifier_kind(id: &Identifier) -> Symbol {
+    match id {
+        Identifier::Account(_) => symbol!("acct"),
+        Identifier::Contract(_) => symbol!("ctrct"),
+        Identifier::Ed25519(_) => symbol!("ed25519"),
+    }
+}
+
+/// Publishes an event under the contract's namespace and `topic`, followed by
+/// `topics`, with the contract's next sequence number prepended to `data` so
+/// that indexers can order every event the contract emits. Chatty topics are
+/// dropped, without using up a sequence number, while verbosity is off.
+pub fn emit<T, D>(e: &Env, topic: Topic, topics: T, data: D)
+where
+    T: IntoVal<Env, Vec<RawVal>>,
+    D: IntoVal<Env, Vec<RawVal>>,
+{
+    if topic.is_chatty() && !is_verbose(e) {
+        return;
+    }
+
+    let seq = read_event_seq(e);
+    write_event_seq(e, seq + 1);
+
+    let mut builder = EventBuilder::new(e)
+        .topic(read_namespace(e))
+        .topic(topic.symbol());
+    for t in topics.into_val(e).iter() {
+        builder = builder.topic(t.unwrap());
+    }
+
+    let mut data = data.into_val(e);
+    data.push_front(seq.into_val(e));
+    record_recent(e, topic, &data);
+
+    builder.data(data).publish(e);
+}
+
+/// Keeps the topic and serialized data of the last `RECENT_EVENTS` events,
+/// newest first, dropping the oldest once full.
+fn record_recent(e: &Env, topic: Topic, data: &Vec<RawVal>) {
+    let mut recent = read_recent(e);
+    recent.push_front((topic.symbol(), data.clone().serialize(e)));
+    if recent.len() > RECENT_EVENTS {
+        recent.pop_back();
+    }
+    write_recent(e, &recent);
+}
+
+/// Builds an event one topic at a time, so that events with any number of
+/// topics up to the host's limit of four can be published without spelling
+/// out the tuple.
+pub struct EventBuilder {
+    env: Env,
+    topics: Vec<RawVal>,
+    data: RawVal,
+}
+
+impl EventBuilder {
+    /// Maximum number of topics an event can have.
+    pub const MAX_TOPICS: u32 = 4;
+
+    /// Creates a builder with no topics and an empty data payload.
+    pub fn new(e: &Env) -> Self {
+        EventBuilder {
+            env: e.clone(),
+            topics: Vec::new(e),
+            data: ().into_val(e),
+        }
+    }
+
+    /// Appends a topic, panicking if the event already has four.
+    pub fn topic<T: IntoVal<Env, RawVal>>(mut self, topic: T) -> Self {
+        if self.topics.len() >= Self::MAX_TOPICS {
+            panic!("events can have at most four topics");
+        }
+        self.topics.push_back(topic.into_val(&self.env));
+        self
+    }
+
+    /// Sets the data payload, replacing any previously set.
+    pub fn data<D: IntoVal<Env, RawVal>>(mut self, data: D) -> Self {
+        self.data = data.into_val(&self.env);
+        self
+    }
+
+    /// Publishes the event.
+    pub fn publish(self, e: &Env) {
+        let events = e.events();
+        let topics = self.topics;
+        let data = self.data;
+        let topic = |i| topics.get_unchecked(i).unwrap();
+        match topics.len() {
+            0 => events.publish((), data),
+            1 => events.publish((topic(0),), data),
+            2 => events.publish((topic(0), topic(1)), data),
+            3 => events.publish((topic(0), topic(1), topic(2)), data),
+            _ => events.publish((topic(0), topic(1), topic(2), topic(3)), data),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::{Env, Symbol};
+
+use crate::error::Error;
+use crate::storage::{is_marked, write_marked};
+
+/// Marks `key` as done the first time it is called, and returns
+/// `AlreadyInitialized` on any later call with the same key.
+pub fn mark_once(e: &Env, key: Symbol) -> Result<(), Error> {
+    if is_marked(e, key) {
+        return Err(Error::AlreadyInitialized);
+    }
+    write_marked(e, key);
+    Ok(())
+}
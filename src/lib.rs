@@ -3,25 +3,897 @@
 #[cfg(feature = "testutils")]
 extern crate std;
 
+mod auth;
+mod balance;
+mod error;
+mod events;
+mod guards;
+mod metrics;
+mod nonce;
+mod roles;
+mod storage;
 mod test;
+pub mod testutils;
 
-use soroban_auth::Identifier;
-use soroban_sdk::{contractimpl, serde::Serialize, symbol, Env};
+use soroban_auth::{verify, Identifier, Signature};
+use soroban_sdk::{
+    contractimpl, serde::Serialize, symbol, BigInt, Bytes, BytesN, Env, Map, Symbol, Vec,
+};
+
+use auth::check_admin;
+use balance::{read_balance, receive_balance, remove_balances, spend_balance, sum_balances};
+pub use error::Error;
+use events::{emit, identifier_kind};
+pub use events::{EventBuilder, InitEvent, LinkEvent, State, Topic};
+use guards::mark_once;
+use metrics::{read_call_count, track, track_view};
+use nonce::{read_nonce, verify_and_consume_nonce};
+use roles::{grant_role, has_role, revoke_role};
+use storage::{
+    has_admin, is_deactivated, is_paused, is_renounced, read_action, read_admin, read_count,
+    read_event_seq, read_init_time, read_last_beat, read_link, read_log, read_max, read_pending,
+    read_recent, read_total, read_value, remove_action, remove_count, remove_pending,
+    remove_values, write_action, write_admin, write_count, write_deactivated, write_init_time,
+    write_last_beat, write_link, write_log, write_max, write_namespace, write_paused,
+    write_pending, write_renounced, write_total, write_value, write_verbose, write_version,
+};
+
+fn check_active(e: &Env) {
+    if is_deactivated(e) {
+        panic!("deactivated");
+    }
+}
+
+fn init_event(e: &Env, admin: Identifier, start: u32) -> InitEvent {
+    InitEvent {
+        admin,
+        version: VERSION,
+        timestamp: e.ledger().timestamp(),
+        start,
+    }
+}
+
+fn withdraw_total(
+    e: &Env,
+    admin: &Signature,
+    nonce: BigInt,
+    fn_name: Symbol,
+    to: Identifier,
+    amount: BigInt,
+) -> Result<(), Error> {
+    check_active(e);
+    check_admin(e, admin)?;
+    if amount < 0 {
+        return Err(Error::NegativeAmount);
+    }
+    let admin_id = admin.identifier(e);
+    verify_and_consume_nonce(e, &admin_id, &nonce);
+    verify(e, admin, fn_name, (admin_id, nonce, &to, &amount));
+
+    let total = read_total(e);
+    if total < amount {
+        return Err(Error::InsufficientFunds);
+    }
+    write_total(e, &(total - amount.clone()));
+
+    emit(e, Topic::Withdraw, (to,), (amount,));
+    Ok(())
+}
+
+/// Version of the contract, published by `init`.
+pub const VERSION: u32 = 1;
 
 /// Contract trait
+///
+/// Every entrypoint is counted in `call_count` except `call_count`, `preview` and `is_admin`.
 pub trait EventsContractTrait {
-    fn init(e: Env, admin: Identifier);
+    fn init(
+        e: Env,
+        admin: Identifier,
+        start: Option<u32>,
+        namespace: Option<Symbol>,
+    ) -> Result<Bytes, Error>;
+
+    fn get_admin(e: Env) -> Result<Identifier, Error>;
+
+    fn set_admin(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        new_admin: Identifier,
+    ) -> Result<(), Error>;
+
+    fn increment(e: Env) -> u32;
+
+    fn get_count(e: Env) -> u32;
+
+    fn pub_custom(e: Env, topic: Symbol, data: Bytes);
+
+    fn pause(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn unpause(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn get_nonce(e: Env, id: Identifier) -> BigInt;
+
+    fn version(e: Env) -> u32;
+
+    fn pub_batch(e: Env, items: Vec<Bytes>) -> u32;
+
+    fn set(e: Env, key: Symbol, value: BigInt);
+
+    fn get(e: Env, key: Symbol) -> Option<BigInt>;
+
+    fn grant_role(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        role: Symbol,
+        who: Identifier,
+    ) -> Result<(), Error>;
+
+    fn revoke(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        role: Symbol,
+        who: Identifier,
+    ) -> Result<(), Error>;
+
+    fn has_role(e: Env, role: Symbol, who: Identifier) -> bool;
+
+    fn init_time(e: Env) -> Result<u64, Error>;
+
+    fn transfer(
+        e: Env,
+        from: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error>;
+
+    fn balance(e: Env, id: Identifier) -> BigInt;
+
+    fn mint(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error>;
+
+    fn reset(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn deactivate(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn deposit(e: Env, from: Identifier, amount: BigInt) -> Result<(), Error>;
+
+    fn withdraw(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error>;
+
+    fn total(e: Env) -> BigInt;
+
+    fn set_if_chg(e: Env, key: Symbol, value: BigInt) -> bool;
+
+    fn link(e: Env, a: Identifier, b: Identifier);
+
+    fn get_link(e: Env) -> Option<LinkEvent>;
+
+    fn migrate(e: Env, admin: Identifier) -> Result<(), Error>;
+
+    fn renounce(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn evt_count(e: Env) -> u64;
+
+    fn pub_expire(e: Env, data: Bytes, ttl_ledgers: u32);
+
+    fn propose(e: Env, admin: Signature, nonce: BigInt, new_admin: Identifier)
+        -> Result<(), Error>;
+
+    fn accept(e: Env, new_admin: Signature, nonce: BigInt) -> Result<(), Error>;
+
+    fn pending(e: Env) -> Option<Identifier>;
+
+    fn call_count(e: Env, fn_name: Symbol) -> u64;
+
+    fn whoami(e: Env) -> BytesN<32>;
+
+    fn verbose(e: Env, admin: Signature, nonce: BigInt, on: bool) -> Result<(), Error>;
+
+    fn preview(e: Env, admin: Identifier, start: Option<u32>) -> Bytes;
+
+    fn soft_wdraw(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<bool, Error>;
+
+    fn snapshot(e: Env) -> Result<State, Error>;
+
+    fn log_values(e: Env, values: Vec<BigInt>);
+
+    fn get_log(e: Env) -> Vec<BigInt>;
+
+    fn schedule(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        action: Symbol,
+        execute_at: u64,
+    ) -> Result<(), Error>;
+
+    fn execute(e: Env, action: Symbol);
+
+    fn set_max(e: Env, admin: Signature, nonce: BigInt, max: u32) -> Result<(), Error>;
+
+    fn is_admin(e: Env, who: Identifier) -> bool;
+
+    fn pub_map(e: Env, fields: Map<Symbol, BigInt>);
+
+    fn supply(e: Env) -> BigInt;
+
+    fn heartbeat(e: Env) -> u32;
+
+    fn last_beat(e: Env) -> u32;
+
+    fn recent(e: Env) -> Vec<(Symbol, Bytes)>;
 }
 
 pub struct EventsContract;
 
 #[contractimpl]
 impl EventsContractTrait for EventsContract {
-    fn init(e: Env, admin: Identifier) {
-        let event = e.events();
-        let t1 = (symbol!("init"),);
+    fn init(
+        e: Env,
+        admin: Identifier,
+        start: Option<u32>,
+        namespace: Option<Symbol>,
+    ) -> Result<Bytes, Error> {
+        mark_once(&e, symbol!("init"))?;
+        let start = start.unwrap_or(0);
+        let kind = identifier_kind(&admin);
+        let data = init_event(&e, admin, start);
+        write_admin(&e, &data.admin);
+        write_init_time(&e, data.timestamp);
+        write_count(&e, start);
+        write_version(&e, VERSION);
+        if let Some(namespace) = namespace {
+            write_namespace(&e, namespace);
+        }
+
+        // mirror the published InitEvent so callers can assert it directly.
+        let data_bytes = data.clone().serialize(&e);
+        emit(&e, Topic::Init, (kind,), (data,));
+
+        track(&e, symbol!("init"));
+        Ok(data_bytes)
+    }
+
+    fn get_admin(e: Env) -> Result<Identifier, Error> {
+        track_view(&e, symbol!("get_admin"));
+        if !has_admin(&e) {
+            return Err(Error::NotInitialized);
+        }
+        if is_renounced(&e) {
+            return Err(Error::Renounced);
+        }
+        Ok(read_admin(&e))
+    }
+
+    fn set_admin(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        new_admin: Identifier,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let old_admin = read_admin(&e);
+        verify_and_consume_nonce(&e, &old_admin, &nonce);
+        verify(
+            &e,
+            &admin,
+            symbol!("set_admin"),
+            (&old_admin, nonce, &new_admin),
+        );
+
+        write_admin(&e, &new_admin);
+        remove_pending(&e);
+
+        emit(
+            &e,
+            Topic::Admin,
+            (symbol!("transfer"),),
+            (old_admin.serialize(&e), new_admin.serialize(&e)),
+        );
+        track(&e, symbol!("set_admin"));
+        Ok(())
+    }
+
+    fn increment(e: Env) -> u32 {
+        track(&e, symbol!("increment"));
+        check_active(&e);
+        if is_paused(&e) {
+            panic!("contract paused");
+        }
+
+        let count = read_count(&e);
+        if let Some(max) = read_max(&e) {
+            // succeed without incrementing, a failed call would discard
+            // the cap event along with the rest of its effects.
+            if count >= max {
+                emit(&e, Topic::Cap, (), (max,));
+                return count;
+            }
+        }
+        let count = count.checked_add(1).expect("counter would exceed u32::MAX");
+        write_count(&e, count);
+
+        emit(&e, Topic::Count, (), (count,));
+
+        count
+    }
+
+    fn get_count(e: Env) -> u32 {
+        track_view(&e, symbol!("get_count"));
+        read_count(&e)
+    }
+
+    fn pub_custom(e: Env, topic: Symbol, data: Bytes) {
+        track(&e, symbol!("pub_custom"));
+        check_active(&e);
+        emit(&e, Topic::Custom, (topic,), (data,));
+    }
+
+    fn pause(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("pause"), (admin_id, nonce));
+
+        write_paused(&e, true);
+
+        emit(&e, Topic::Paused, (), ());
+        track(&e, symbol!("pause"));
+        Ok(())
+    }
+
+    fn unpause(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("unpause"), (admin_id, nonce));
+
+        write_paused(&e, false);
+
+        emit(&e, Topic::Unpaused, (), ());
+        track(&e, symbol!("unpause"));
+        Ok(())
+    }
+
+    fn get_nonce(e: Env, id: Identifier) -> BigInt {
+        track_view(&e, symbol!("get_nonce"));
+        read_nonce(&e, &id)
+    }
+
+    fn version(e: Env) -> u32 {
+        track_view(&e, symbol!("version"));
+        VERSION
+    }
+
+    fn pub_batch(e: Env, items: Vec<Bytes>) -> u32 {
+        track(&e, symbol!("pub_batch"));
+        check_active(&e);
+        let mut count = 0;
+        for item in items.iter() {
+            emit(&e, Topic::Batch, (), (count, item.unwrap()));
+            count += 1;
+        }
+
+        count
+    }
+
+    fn set(e: Env, key: Symbol, value: BigInt) {
+        track(&e, symbol!("set"));
+        check_active(&e);
+        let old_value = read_value(&e, key);
+        write_value(&e, key, &value);
+
+        emit(&e, Topic::Set, (key,), (old_value, value));
+    }
+
+    fn get(e: Env, key: Symbol) -> Option<BigInt> {
+        track_view(&e, symbol!("get"));
+        read_value(&e, key)
+    }
+
+    fn grant_role(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        role: Symbol,
+        who: Identifier,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(
+            &e,
+            &admin,
+            symbol!("grant_role"),
+            (admin_id, nonce, role, &who),
+        );
+
+        grant_role(&e, role, &who);
+
+        emit(&e, Topic::Role, (symbol!("grant"),), (role, who));
+        track(&e, symbol!("grant_role"));
+        Ok(())
+    }
+
+    fn revoke(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        role: Symbol,
+        who: Identifier,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("revoke"), (admin_id, nonce, role, &who));
+
+        revoke_role(&e, role, &who);
+
+        emit(&e, Topic::Role, (symbol!("revoke"),), (role, who));
+        track(&e, symbol!("revoke"));
+        Ok(())
+    }
+
+    fn has_role(e: Env, role: Symbol, who: Identifier) -> bool {
+        track_view(&e, symbol!("has_role"));
+        has_role(&e, role, &who)
+    }
+
+    fn init_time(e: Env) -> Result<u64, Error> {
+        track_view(&e, symbol!("init_time"));
+        read_init_time(&e).ok_or(Error::NotInitialized)
+    }
+
+    fn transfer(
+        e: Env,
+        from: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        if amount < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        let from_id = from.identifier(&e);
+        verify_and_consume_nonce(&e, &from_id, &nonce);
+        verify(
+            &e,
+            &from,
+            symbol!("transfer"),
+            (&from_id, nonce, &to, &amount),
+        );
+
+        spend_balance(&e, &from_id, &amount)?;
+        receive_balance(&e, &to, &amount);
+
+        emit(&e, Topic::Transfer, (from_id, to), (amount,));
+        track(&e, symbol!("transfer"));
+        Ok(())
+    }
+
+    fn balance(e: Env, id: Identifier) -> BigInt {
+        track_view(&e, symbol!("balance"));
+        read_balance(&e, &id)
+    }
+
+    fn mint(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        if amount < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("mint"), (admin_id, nonce, &to, &amount));
+
+        receive_balance(&e, &to, &amount);
+
+        emit(&e, Topic::Mint, (to,), (amount,));
+        track(&e, symbol!("mint"));
+        Ok(())
+    }
+
+    fn reset(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("reset"), (admin_id, nonce));
+
+        remove_count(&e);
+        remove_balances(&e);
+        remove_values(&e);
+
+        emit(&e, Topic::Reset, (), ());
+        track(&e, symbol!("reset"));
+        Ok(())
+    }
+
+    fn deactivate(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("deactivate"), (admin_id, nonce));
+        // counted before the write, metrics stop once deactivated.
+        track(&e, symbol!("deactivate"));
+
+        write_deactivated(&e);
+
+        emit(&e, Topic::Dead, (), ());
+        Ok(())
+    }
+
+    fn deposit(e: Env, from: Identifier, amount: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        if amount < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        write_total(&e, &(read_total(&e) + amount.clone()));
+
+        emit(&e, Topic::Deposit, (from,), (amount,));
+        track(&e, symbol!("deposit"));
+        Ok(())
+    }
+
+    fn withdraw(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<(), Error> {
+        withdraw_total(&e, &admin, nonce, symbol!("withdraw"), to, amount)?;
+        track(&e, symbol!("withdraw"));
+        Ok(())
+    }
+
+    fn total(e: Env) -> BigInt {
+        track_view(&e, symbol!("total"));
+        read_total(&e)
+    }
+
+    fn set_if_chg(e: Env, key: Symbol, value: BigInt) -> bool {
+        track(&e, symbol!("set_if_chg"));
+        check_active(&e);
+        if read_value(&e, key) == Some(value.clone()) {
+            return false;
+        }
+        write_value(&e, key, &value);
+
+        emit(&e, Topic::Changed, (key,), (value,));
+        true
+    }
+
+    fn link(e: Env, a: Identifier, b: Identifier) {
+        track(&e, symbol!("link"));
+        check_active(&e);
+        let link = LinkEvent {
+            a,
+            b,
+            ledger: e.ledger().sequence(),
+        };
+        write_link(&e, &link);
+
+        emit(&e, Topic::Link, (), (link,));
+    }
+
+    fn get_link(e: Env) -> Option<LinkEvent> {
+        track_view(&e, symbol!("get_link"));
+        read_link(&e)
+    }
+
+    fn migrate(e: Env, admin: Identifier) -> Result<(), Error> {
+        check_active(&e);
+        // instances deployed before the admin was stored have nothing to
+        // migrate from, anything else has already been initialized.
+        if has_admin(&e) {
+            return Err(Error::AlreadyInitialized);
+        }
+        // a migrated instance counts as initialized.
+        mark_once(&e, symbol!("init"))?;
+        write_admin(&e, &admin);
+        write_version(&e, VERSION);
+
+        emit(&e, Topic::Migrate, (), (VERSION,));
+        track(&e, symbol!("migrate"));
+        Ok(())
+    }
+
+    fn renounce(e: Env, admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("renounce"), (&admin_id, nonce));
+
+        write_renounced(&e);
+        remove_pending(&e);
+
+        emit(&e, Topic::Admin, (symbol!("renounce"),), (admin_id,));
+        track(&e, symbol!("renounce"));
+        Ok(())
+    }
+
+    fn evt_count(e: Env) -> u64 {
+        track_view(&e, symbol!("evt_count"));
+        // every event goes through emit, so the next sequence number is also
+        // the number of events published so far.
+        read_event_seq(&e)
+    }
+
+    fn pub_expire(e: Env, data: Bytes, ttl_ledgers: u32) {
+        track(&e, symbol!("pub_expire"));
+        check_active(&e);
+        let expires_at = e.ledger().sequence().saturating_add(ttl_ledgers);
+
+        emit(&e, Topic::Ttl, (), (data, expires_at));
+    }
+
+    fn propose(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        new_admin: Identifier,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(
+            &e,
+            &admin,
+            symbol!("propose"),
+            (&admin_id, nonce, &new_admin),
+        );
+
+        write_pending(&e, &admin_id, &new_admin);
+
+        emit(&e, Topic::Admin, (symbol!("proposed"),), (new_admin,));
+        track(&e, symbol!("propose"));
+        Ok(())
+    }
+
+    fn accept(e: Env, new_admin: Signature, nonce: BigInt) -> Result<(), Error> {
+        check_active(&e);
+        if !has_admin(&e) {
+            return Err(Error::NotInitialized);
+        }
+        let new_admin_id = new_admin.identifier(&e);
+        let old_admin = read_admin(&e);
+        // the proposal only stands while its proposer is still the admin.
+        if read_pending(&e) != Some((old_admin.clone(), new_admin_id.clone())) {
+            return Err(Error::Unauthorized);
+        }
+        verify_and_consume_nonce(&e, &new_admin_id, &nonce);
+        verify(&e, &new_admin, symbol!("accept"), (&new_admin_id, nonce));
+
+        write_admin(&e, &new_admin_id);
+        remove_pending(&e);
+
+        emit(
+            &e,
+            Topic::Admin,
+            (symbol!("accepted"),),
+            (old_admin, new_admin_id),
+        );
+        track(&e, symbol!("accept"));
+        Ok(())
+    }
+
+    fn pending(e: Env) -> Option<Identifier> {
+        track_view(&e, symbol!("pending"));
+        read_pending(&e).map(|(_, id)| id)
+    }
+
+    fn call_count(e: Env, fn_name: Symbol) -> u64 {
+        read_call_count(&e, fn_name)
+    }
+
+    fn whoami(e: Env) -> BytesN<32> {
+        track(&e, symbol!("whoami"));
+        check_active(&e);
+        let contract_id = e.current_contract();
+
+        emit(&e, Topic::Whoami, (), (contract_id.clone(),));
+        contract_id
+    }
+
+    fn verbose(e: Env, admin: Signature, nonce: BigInt, on: bool) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("verbose"), (admin_id, nonce, on));
+
+        write_verbose(&e, on);
+
+        emit(&e, Topic::Verbose, (), (on,));
+        track(&e, symbol!("verbose"));
+        Ok(())
+    }
+
+    fn preview(e: Env, admin: Identifier, start: Option<u32>) -> Bytes {
+        // not tracked, a preview must leave storage untouched.
+        init_event(&e, admin, start.unwrap_or(0)).serialize(&e)
+    }
+
+    fn soft_wdraw(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        to: Identifier,
+        amount: BigInt,
+    ) -> Result<bool, Error> {
+        let fn_name = symbol!("soft_wdraw");
+        let withdrawn = match withdraw_total(&e, &admin, nonce, fn_name, to, amount.clone()) {
+            Ok(()) => true,
+            Err(Error::InsufficientFunds) => {
+                emit(&e, Topic::Failed, (symbol!("funds"),), (amount,));
+                false
+            }
+            Err(err) => return Err(err),
+        };
+        track(&e, fn_name);
+        Ok(withdrawn)
+    }
+
+    fn snapshot(e: Env) -> Result<State, Error> {
+        check_active(&e);
+        if !has_admin(&e) {
+            return Err(Error::NotInitialized);
+        }
+        if is_renounced(&e) {
+            return Err(Error::Renounced);
+        }
+        let state = State {
+            admin: read_admin(&e),
+            count: read_count(&e),
+            total: read_total(&e),
+        };
+
+        emit(&e, Topic::Snapshot, (), (state.clone(),));
+        track(&e, symbol!("snapshot"));
+        Ok(state)
+    }
+
+    fn log_values(e: Env, values: Vec<BigInt>) {
+        track(&e, symbol!("log_values"));
+        check_active(&e);
+        if values.is_empty() {
+            return;
+        }
+        let mut log = read_log(&e);
+        log.append(&values);
+        write_log(&e, &log);
+
+        emit(&e, Topic::Log, (), (values,));
+    }
+
+    fn get_log(e: Env) -> Vec<BigInt> {
+        track_view(&e, symbol!("get_log"));
+        read_log(&e)
+    }
+
+    fn schedule(
+        e: Env,
+        admin: Signature,
+        nonce: BigInt,
+        action: Symbol,
+        execute_at: u64,
+    ) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(
+            &e,
+            &admin,
+            symbol!("schedule"),
+            (admin_id, nonce, action, execute_at),
+        );
+
+        write_action(&e, action, execute_at);
+
+        emit(&e, Topic::Scheduled, (action,), (execute_at,));
+        track(&e, symbol!("schedule"));
+        Ok(())
+    }
+
+    fn execute(e: Env, action: Symbol) {
+        track(&e, symbol!("execute"));
+        check_active(&e);
+        let execute_at = read_action(&e, action).expect("action not scheduled");
+        if e.ledger().timestamp() < execute_at {
+            panic!("action is time-locked");
+        }
+        remove_action(&e, action);
+
+        emit(&e, Topic::Executed, (action,), ());
+    }
+
+    fn set_max(e: Env, admin: Signature, nonce: BigInt, max: u32) -> Result<(), Error> {
+        check_active(&e);
+        check_admin(&e, &admin)?;
+        let admin_id = admin.identifier(&e);
+        verify_and_consume_nonce(&e, &admin_id, &nonce);
+        verify(&e, &admin, symbol!("set_max"), (admin_id, nonce, max));
+
+        write_max(&e, max);
+        track(&e, symbol!("set_max"));
+        Ok(())
+    }
+
+    fn is_admin(e: Env, who: Identifier) -> bool {
+        // not tracked, the check must leave storage untouched.
+        has_admin(&e) && !is_renounced(&e) && read_admin(&e) == who
+    }
+
+    fn pub_map(e: Env, fields: Map<Symbol, BigInt>) {
+        track(&e, symbol!("pub_map"));
+        check_active(&e);
+        emit(&e, Topic::Map, (), (fields,));
+    }
+
+    fn supply(e: Env) -> BigInt {
+        track(&e, symbol!("supply"));
+        check_active(&e);
+        let supply = sum_balances(&e);
+
+        emit(&e, Topic::Supply, (), (supply.clone(),));
+        supply
+    }
+
+    fn heartbeat(e: Env) -> u32 {
+        track(&e, symbol!("heartbeat"));
+        check_active(&e);
+        let sequence = e.ledger().sequence();
+        write_last_beat(&e, sequence);
+
+        emit(&e, Topic::Beat, (), (sequence,));
+        sequence
+    }
+
+    fn last_beat(e: Env) -> u32 {
+        track_view(&e, symbol!("last_beat"));
+        read_last_beat(&e)
+    }
 
-        let id_bytes = admin.serialize(&e);
-        event.publish(t1, (id_bytes,));
+    fn recent(e: Env) -> Vec<(Symbol, Bytes)> {
+        track_view(&e, symbol!("recent"));
+        read_recent(&e)
     }
 }
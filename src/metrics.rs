@@ -0,0 +1,41 @@
+use soroban_sdk::{Env, Symbol};
+
+use crate::events::{emit, Topic};
+use crate::storage::{is_deactivated, read_calls, write_calls};
+
+/// Number of calls to a function between two metric events for it.
+pub const METRIC_INTERVAL: u64 = 100;
+
+/// Counts a call to `fn_name`, publishing a metric event every
+/// `METRIC_INTERVAL` calls. Entrypoints returning a `Result` call this only
+/// once they have succeeded, so a failed call leaves no metric event behind.
+pub fn track(e: &Env, fn_name: Symbol) {
+    if let Some(count) = count_call(e, fn_name) {
+        if count.is_multiple_of(METRIC_INTERVAL) {
+            emit(e, Topic::Metric, (fn_name,), (count,));
+        }
+    }
+}
+
+/// Counts a call to the view `fn_name`. Views are still counted, but never
+/// publish metric events so that reading state does not emit anything.
+pub fn track_view(e: &Env, fn_name: Symbol) {
+    count_call(e, fn_name);
+}
+
+/// Increments the call count of `fn_name` and returns it, or does nothing once
+/// the contract has been deactivated since it must no longer write storage.
+fn count_call(e: &Env, fn_name: Symbol) -> Option<u64> {
+    if is_deactivated(e) {
+        return None;
+    }
+    let mut calls = read_calls(e);
+    let count = calls.get(fn_name).unwrap_or(Ok(0)).unwrap() + 1;
+    calls.set(fn_name, count);
+    write_calls(e, &calls);
+    Some(count)
+}
+
+pub fn read_call_count(e: &Env, fn_name: Symbol) -> u64 {
+    read_calls(e).get(fn_name).unwrap_or(Ok(0)).unwrap()
+}
@@ -0,0 +1,32 @@
+use soroban_auth::Identifier;
+use soroban_sdk::{BigInt, Env};
+
+use crate::storage::DataKey;
+
+pub fn read_nonce(e: &Env, id: &Identifier) -> BigInt {
+    let key = DataKey::Nonce(id.clone());
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(BigInt::zero(e)))
+        .unwrap()
+}
+
+pub fn verify_and_consume_nonce(e: &Env, id: &Identifier, expected_nonce: &BigInt) {
+    // replay protection is not required for invoker authorization of
+    // contracts because there's no cryptographic signature involved, so
+    // contracts are expected to always pass 0.
+    if matches!(id, Identifier::Contract(_)) {
+        if BigInt::zero(e) != expected_nonce {
+            panic!("nonce should be zero for contract")
+        }
+        return;
+    }
+
+    let key = DataKey::Nonce(id.clone());
+    let nonce = read_nonce(e, id);
+
+    if nonce != expected_nonce {
+        panic!("incorrect nonce")
+    }
+    e.data().set(key, &nonce + 1);
+}
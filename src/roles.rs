@@ -0,0 +1,27 @@
+use soroban_auth::Identifier;
+use soroban_sdk::{Env, Set, Symbol};
+
+use crate::storage::DataKey;
+
+fn read_members(e: &Env, role: Symbol) -> Set<Identifier> {
+    e.data()
+        .get(DataKey::Role(role))
+        .unwrap_or_else(|| Ok(Set::new(e)))
+        .unwrap()
+}
+
+pub fn has_role(e: &Env, role: Symbol, who: &Identifier) -> bool {
+    read_members(e, role).contains(who.clone())
+}
+
+pub fn grant_role(e: &Env, role: Symbol, who: &Identifier) {
+    let mut members = read_members(e, role);
+    members.insert(who.clone());
+    e.data().set(DataKey::Role(role), members);
+}
+
+pub fn revoke_role(e: &Env, role: Symbol, who: &Identifier) {
+    let mut members = read_members(e, role);
+    members.remove(who.clone());
+    e.data().set(DataKey::Role(role), members);
+}
@@ -0,0 +1,254 @@
+use soroban_auth::Identifier;
+use soroban_sdk::{contracttype, symbol, BigInt, Bytes, Env, Map, Set, Symbol, Vec};
+
+use crate::events::LinkEvent;
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Count,
+    Paused,
+    Nonce(Identifier),
+    Value(Symbol),
+    Role(Symbol),
+    InitTime,
+    Balances,
+    Keys,
+    EventSeq,
+    Dead,
+    Total,
+    Link,
+    Version,
+    Once(Symbol),
+    Pending,
+    Calls,
+    Verbose,
+    Log,
+    Action(Symbol),
+    Max,
+    LastBeat,
+    Namespace,
+    Recent,
+    Renounced,
+}
+
+pub fn has_admin(e: &Env) -> bool {
+    e.data().has(DataKey::Admin)
+}
+
+pub fn read_admin(e: &Env) -> Identifier {
+    e.data().get_unchecked(DataKey::Admin).unwrap()
+}
+
+pub fn write_admin(e: &Env, admin: &Identifier) {
+    e.data().set(DataKey::Admin, admin);
+}
+
+pub fn read_count(e: &Env) -> u32 {
+    e.data().get(DataKey::Count).unwrap_or(Ok(0)).unwrap()
+}
+
+pub fn write_count(e: &Env, count: u32) {
+    e.data().set(DataKey::Count, count);
+}
+
+pub fn remove_count(e: &Env) {
+    e.data().remove(DataKey::Count);
+}
+
+pub fn is_paused(e: &Env) -> bool {
+    e.data().get(DataKey::Paused).unwrap_or(Ok(false)).unwrap()
+}
+
+pub fn write_paused(e: &Env, paused: bool) {
+    e.data().set(DataKey::Paused, paused);
+}
+
+pub fn is_deactivated(e: &Env) -> bool {
+    e.data().has(DataKey::Dead)
+}
+
+pub fn write_deactivated(e: &Env) {
+    e.data().set(DataKey::Dead, true);
+}
+
+pub fn read_value(e: &Env, key: Symbol) -> Option<BigInt> {
+    e.data()
+        .get(DataKey::Value(key))
+        .map(|value| value.unwrap())
+}
+
+pub fn write_value(e: &Env, key: Symbol, value: &BigInt) {
+    let mut keys = read_keys(e);
+    keys.insert(key);
+    e.data().set(DataKey::Keys, keys);
+    e.data().set(DataKey::Value(key), value);
+}
+
+fn read_keys(e: &Env) -> Set<Symbol> {
+    e.data()
+        .get(DataKey::Keys)
+        .unwrap_or_else(|| Ok(Set::new(e)))
+        .unwrap()
+}
+
+pub fn remove_values(e: &Env) {
+    for key in read_keys(e).iter() {
+        e.data().remove(DataKey::Value(key.unwrap()));
+    }
+    e.data().remove(DataKey::Keys);
+}
+
+pub fn read_init_time(e: &Env) -> Option<u64> {
+    e.data()
+        .get(DataKey::InitTime)
+        .map(|timestamp| timestamp.unwrap())
+}
+
+pub fn write_init_time(e: &Env, timestamp: u64) {
+    e.data().set(DataKey::InitTime, timestamp);
+}
+
+pub fn read_event_seq(e: &Env) -> u64 {
+    e.data().get(DataKey::EventSeq).unwrap_or(Ok(0)).unwrap()
+}
+
+pub fn write_event_seq(e: &Env, seq: u64) {
+    e.data().set(DataKey::EventSeq, seq);
+}
+
+pub fn read_total(e: &Env) -> BigInt {
+    e.data()
+        .get(DataKey::Total)
+        .unwrap_or_else(|| Ok(BigInt::zero(e)))
+        .unwrap()
+}
+
+pub fn write_total(e: &Env, total: &BigInt) {
+    e.data().set(DataKey::Total, total);
+}
+
+pub fn read_link(e: &Env) -> Option<LinkEvent> {
+    e.data().get(DataKey::Link).map(|link| link.unwrap())
+}
+
+pub fn write_link(e: &Env, link: &LinkEvent) {
+    e.data().set(DataKey::Link, link);
+}
+
+pub fn write_version(e: &Env, version: u32) {
+    e.data().set(DataKey::Version, version);
+}
+
+pub fn is_marked(e: &Env, key: Symbol) -> bool {
+    e.data().has(DataKey::Once(key))
+}
+
+pub fn write_marked(e: &Env, key: Symbol) {
+    e.data().set(DataKey::Once(key), true);
+}
+
+/// Returns the admin that made the pending proposal and the proposed admin.
+pub fn read_pending(e: &Env) -> Option<(Identifier, Identifier)> {
+    e.data()
+        .get(DataKey::Pending)
+        .map(|pending| pending.unwrap())
+}
+
+pub fn write_pending(e: &Env, proposer: &Identifier, id: &Identifier) {
+    e.data().set(DataKey::Pending, (proposer, id));
+}
+
+pub fn remove_pending(e: &Env) {
+    e.data().remove(DataKey::Pending);
+}
+
+pub fn read_calls(e: &Env) -> Map<Symbol, u64> {
+    e.data()
+        .get(DataKey::Calls)
+        .unwrap_or_else(|| Ok(Map::new(e)))
+        .unwrap()
+}
+
+pub fn write_calls(e: &Env, calls: &Map<Symbol, u64>) {
+    e.data().set(DataKey::Calls, calls);
+}
+
+pub fn is_verbose(e: &Env) -> bool {
+    e.data().get(DataKey::Verbose).unwrap_or(Ok(true)).unwrap()
+}
+
+pub fn write_verbose(e: &Env, on: bool) {
+    e.data().set(DataKey::Verbose, on);
+}
+
+pub fn read_log(e: &Env) -> Vec<BigInt> {
+    e.data()
+        .get(DataKey::Log)
+        .unwrap_or_else(|| Ok(Vec::new(e)))
+        .unwrap()
+}
+
+pub fn write_log(e: &Env, log: &Vec<BigInt>) {
+    e.data().set(DataKey::Log, log);
+}
+
+pub fn read_action(e: &Env, action: Symbol) -> Option<u64> {
+    e.data()
+        .get(DataKey::Action(action))
+        .map(|execute_at| execute_at.unwrap())
+}
+
+pub fn write_action(e: &Env, action: Symbol, execute_at: u64) {
+    e.data().set(DataKey::Action(action), execute_at);
+}
+
+pub fn remove_action(e: &Env, action: Symbol) {
+    e.data().remove(DataKey::Action(action));
+}
+
+pub fn read_max(e: &Env) -> Option<u32> {
+    e.data().get(DataKey::Max).map(|max| max.unwrap())
+}
+
+pub fn write_max(e: &Env, max: u32) {
+    e.data().set(DataKey::Max, max);
+}
+
+pub fn read_last_beat(e: &Env) -> u32 {
+    e.data().get(DataKey::LastBeat).unwrap_or(Ok(0)).unwrap()
+}
+
+pub fn write_last_beat(e: &Env, sequence: u32) {
+    e.data().set(DataKey::LastBeat, sequence);
+}
+
+pub fn read_namespace(e: &Env) -> Symbol {
+    e.data()
+        .get(DataKey::Namespace)
+        .unwrap_or(Ok(symbol!("evt")))
+        .unwrap()
+}
+
+pub fn write_namespace(e: &Env, namespace: Symbol) {
+    e.data().set(DataKey::Namespace, namespace);
+}
+
+pub fn read_recent(e: &Env) -> Vec<(Symbol, Bytes)> {
+    e.data()
+        .get(DataKey::Recent)
+        .unwrap_or_else(|| Ok(Vec::new(e)))
+        .unwrap()
+}
+
+pub fn write_recent(e: &Env, recent: &Vec<(Symbol, Bytes)>) {
+    e.data().set(DataKey::Recent, recent);
+}
+
+pub fn is_renounced(e: &Env) -> bool {
+    e.data().has(DataKey::Renounced)
+}
+
+pub fn write_renounced(e: &Env) {
+    e.data().set(DataKey::Renounced, true);
+}
@@ -1,8 +1,25 @@
 #![cfg(test)]
 
-use super::{EventsContract, EventsContractClient};
+use super::{
+    events::RECENT_EVENTS,
+    guards::mark_once,
+    metrics::{track, METRIC_INTERVAL},
+    testutils::{events_with_topic, last_event},
+    Error, EventBuilder, EventsContract, EventsContractClient, InitEvent, LinkEvent, State, Topic,
+    VERSION,
+};
 
-use soroban_sdk::Env;
+use soroban_auth::{
+    testutils::ed25519::{generate, sign},
+    Ed25519Signature, Identifier, Signature,
+};
+use soroban_sdk::{
+    contractimpl, map,
+    serde::Serialize,
+    symbol,
+    testutils::{Events, Ledger},
+    vec, BigInt, Bytes, BytesN, Env, IntoVal, Map, RawVal, Status, Symbol, TryIntoVal, Vec,
+};
 
 #[test]
 fn test_types() {
@@ -11,7 +28,2135 @@ fn test_types() {
     let contract_id = env.register_contract(None, EventsContract);
     let client = EventsContractClient::new(&env, contract_id);
 
-    let (admin_id, _) = soroban_auth::testutils::ed25519::generate(&env);
+    let (admin_id, _) = generate(&env);
 
-    client.init(&admin_id);
+    let data_bytes = client.init(&admin_id, &None, &None);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("init"), symbol!("ed25519")).into_val(&env);
+    let expected_event = InitEvent {
+        admin: admin_id,
+        version: VERSION,
+        timestamp: 0,
+        start: 0,
+    };
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event, expected_event);
+    assert_eq!(data_bytes, expected_event.serialize(&env));
+}
+
+#[test]
+fn test_get_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(client.get_admin(), admin_id);
+}
+
+#[test]
+fn test_init_twice() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(
+        client.try_init(&admin_id, &None, &None),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_set_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (new_admin_id, _) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("set_admin"),
+        (&admin_id, BigInt::zero(&env), &new_admin_id),
+    );
+    client.set_admin(&sig, &BigInt::zero(&env), &new_admin_id);
+    assert_eq!(client.get_admin(), new_admin_id);
+
+    let (event_contract_id, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("admin"), symbol!("transfer")).into_val(&env);
+    let (_, old_bytes, new_bytes): (u64, Bytes, Bytes) = data.try_into_val(&env).unwrap();
+
+    assert_eq!(event_contract_id, contract_id);
+    assert_eq!(topics, expected_topics);
+    assert_eq!(old_bytes, admin_id.serialize(&env));
+    assert_eq!(new_bytes, new_admin_id.serialize(&env));
+}
+
+#[test]
+fn test_set_admin_uninitialized() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (new_admin_id, _) = generate(&env);
+
+    assert_eq!(
+        client.try_set_admin(&Signature::Invoker, &BigInt::zero(&env), &new_admin_id),
+        Err(Ok(Error::NotInitialized))
+    );
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::NotInitialized)));
+}
+
+#[test]
+fn test_increment() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.get_count(), 0);
+    assert_eq!(client.increment(), 1);
+    assert_eq!(client.increment(), 2);
+    assert_eq!(client.increment(), 3);
+    assert_eq!(client.get_count(), 3);
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 3);
+
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("count")).into_val(&env);
+    for (i, event) in events.iter().enumerate() {
+        let (_, topics, data) = event.unwrap();
+        let (_, count): (u64, u32) = data.try_into_val(&env).unwrap();
+
+        assert_eq!(topics, expected_topics);
+        assert_eq!(count, i as u32 + 1);
+    }
+}
+
+#[test]
+fn test_pub_custom() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let data = Bytes::from_array(&env, &[1, 2, 3]);
+    client.pub_custom(&symbol!("my_topic"), &data);
+
+    let (_, topics, event_data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("custom"), symbol!("my_topic")).into_val(&env);
+    let (_, event_data): (u64, Bytes) = event_data.try_into_val(&env).unwrap();
+
+    assert_eq!(topics.len(), 3);
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_data, data);
+}
+
+#[test]
+fn test_pause() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.pause(&Signature::Invoker, &BigInt::zero(&env));
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("paused")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+}
+
+#[test]
+#[should_panic(expected = "contract paused")]
+fn test_increment_paused() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.pause(&Signature::Invoker, &BigInt::zero(&env));
+    client.increment();
+}
+
+#[test]
+fn test_unpause() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.pause(&Signature::Invoker, &BigInt::zero(&env));
+    client.unpause(&Signature::Invoker, &BigInt::from_u32(&env, 1));
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("unpaused")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    assert_eq!(client.increment(), 1);
+}
+
+#[test]
+fn test_pause_signed() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("pause"),
+        (&admin_id, BigInt::zero(&env)),
+    );
+    client.pause(&sig, &BigInt::zero(&env));
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("paused")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+}
+
+#[test]
+fn test_pause_not_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, _) = generate(&env);
+    let (other_id, other_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    let sig = sign(
+        &env,
+        &other_signer,
+        &contract_id,
+        symbol!("pause"),
+        (&other_id, BigInt::zero(&env)),
+    );
+    assert_eq!(
+        client.try_pause(&sig, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError")]
+fn test_pause_bad_signature() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("unpause"),
+        (&admin_id, BigInt::zero(&env)),
+    );
+    client.pause(&sig, &BigInt::zero(&env));
+}
+
+#[test]
+fn test_nonce() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(client.get_nonce(&admin_id), BigInt::zero(&env));
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("pause"),
+        (&admin_id, &nonce),
+    );
+    client.pause(&sig, &nonce);
+    assert_eq!(client.get_nonce(&admin_id), BigInt::from_u32(&env, 1));
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("unpause"),
+        (&admin_id, &nonce),
+    );
+    client.unpause(&sig, &nonce);
+    assert_eq!(client.get_nonce(&admin_id), BigInt::from_u32(&env, 2));
+}
+
+#[test]
+#[should_panic(expected = "incorrect nonce")]
+fn test_nonce_reuse() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+
+    let nonce = BigInt::zero(&env);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("pause"),
+        (&admin_id, &nonce),
+    );
+    client.pause(&sig, &nonce);
+    client.pause(&sig, &nonce);
+}
+
+#[test]
+fn test_version() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.version(), VERSION);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+
+    let (_, _, data) = last_event(&env);
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(event.version, VERSION);
+}
+
+#[test]
+fn test_pub_batch() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let items = vec![
+        &env,
+        Bytes::from_array(&env, &[1]),
+        Bytes::from_array(&env, &[2, 2]),
+        Bytes::from_array(&env, &[3, 3, 3]),
+    ];
+    assert_eq!(client.pub_batch(&items), 3);
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 3);
+
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("batch")).into_val(&env);
+    for (i, event) in events.iter().enumerate() {
+        let (_, topics, data) = event.unwrap();
+        let (_, index, item): (u64, u32, Bytes) = data.try_into_val(&env).unwrap();
+
+        assert_eq!(topics, expected_topics);
+        assert_eq!(index, i as u32);
+        assert_eq!(item, items.get_unchecked(index).unwrap());
+    }
+}
+
+#[test]
+fn test_pub_batch_empty() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.pub_batch(&Vec::new(&env)), 0);
+    assert!(env.events().all().is_empty());
+}
+
+#[test]
+fn test_set_get() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let key = symbol!("key");
+    let first = BigInt::from_i64(&env, 10);
+    let second = BigInt::from_i64(&env, -20);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("set"), key).into_val(&env);
+
+    assert_eq!(client.get(&key), None);
+
+    client.set(&key, &first);
+    assert_eq!(client.get(&key), Some(first.clone()));
+
+    let (_, topics, data) = last_event(&env);
+    let (_, old, new): (u64, Option<BigInt>, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(old, None);
+    assert_eq!(new, first);
+
+    client.set(&key, &second);
+    assert_eq!(client.get(&key), Some(second.clone()));
+
+    let (_, topics, data) = last_event(&env);
+    let (_, old, new): (u64, Option<BigInt>, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(old, Some(first));
+    assert_eq!(new, second);
+}
+
+#[test]
+fn test_roles() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (user_id, _) = generate(&env);
+    let role = symbol!("minter");
+
+    client.init(&admin_id, &None, &None);
+    assert!(!client.has_role(&role, &user_id));
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("grant_role"),
+        (&admin_id, &nonce, &role, &user_id),
+    );
+    client.grant_role(&sig, &nonce, &role, &user_id);
+    assert!(client.has_role(&role, &user_id));
+    assert!(!client.has_role(&symbol!("burner"), &user_id));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("role"), symbol!("grant")).into_val(&env);
+    let (_, event_role, event_id): (u64, Symbol, Identifier) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_role, role);
+    assert_eq!(event_id, user_id);
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("revoke"),
+        (&admin_id, &nonce, &role, &user_id),
+    );
+    client.revoke(&sig, &nonce, &role, &user_id);
+    assert!(!client.has_role(&role, &user_id));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("role"), symbol!("revoke")).into_val(&env);
+    let (_, event_role, event_id): (u64, Symbol, Identifier) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_role, role);
+    assert_eq!(event_id, user_id);
+}
+
+#[test]
+fn test_grant_not_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, _) = generate(&env);
+    let (user_id, user_signer) = generate(&env);
+    let role = symbol!("minter");
+
+    client.init(&admin_id, &None, &None);
+
+    let nonce = client.get_nonce(&user_id);
+    let sig = sign(
+        &env,
+        &user_signer,
+        &contract_id,
+        symbol!("grant_role"),
+        (&user_id, &nonce, &role, &user_id),
+    );
+    assert_eq!(
+        client.try_grant_role(&sig, &nonce, &role, &user_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(!client.has_role(&role, &user_id));
+}
+
+#[test]
+fn test_init_time() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 12345);
+    assert_eq!(client.try_init_time(), Err(Ok(Error::NotInitialized)));
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(client.init_time(), 12345);
+
+    let (_, _, data) = last_event(&env);
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(event.timestamp, 12345);
+
+    env.ledger().with_mut(|li| li.timestamp = 99999);
+    assert_eq!(client.init_time(), 12345);
+}
+
+#[test]
+fn test_transfer() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let from_id = Identifier::Account(env.source_account());
+    let (to_id, _) = generate(&env);
+    let zero = BigInt::zero(&env);
+
+    client.transfer(&Signature::Invoker, &zero, &to_id, &zero);
+    assert_eq!(client.balance(&from_id), zero);
+    assert_eq!(client.balance(&to_id), zero);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("transfer"), &from_id, &to_id).into_val(&env);
+    let (_, amount): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(amount, zero);
+}
+
+#[test]
+fn test_transfer_errors() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let from_id = Identifier::Account(env.source_account());
+    let (to_id, _) = generate(&env);
+
+    assert_eq!(
+        client.try_transfer(
+            &Signature::Invoker,
+            &client.get_nonce(&from_id),
+            &to_id,
+            &BigInt::from_i64(&env, -1)
+        ),
+        Err(Ok(Error::NegativeAmount))
+    );
+    assert_eq!(
+        client.try_transfer(
+            &Signature::Invoker,
+            &client.get_nonce(&from_id),
+            &to_id,
+            &BigInt::from_i64(&env, 1)
+        ),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert!(env.events().all().is_empty());
+}
+
+#[test]
+fn test_transfer_not_owner() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (victim_id, _) = generate(&env);
+    let (thief_id, thief_signer) = generate(&env);
+    let amount = BigInt::from_u32(&env, 50);
+
+    client.init(&admin_id, &None, &None);
+    client.mint(
+        &Signature::Invoker,
+        &BigInt::zero(&env),
+        &victim_id,
+        &amount,
+    );
+
+    // the thief signs a transfer out of the victim's balance, claiming the
+    // victim's public key.
+    let nonce = client.get_nonce(&victim_id);
+    let sig = match sign(
+        &env,
+        &thief_signer,
+        &contract_id,
+        symbol!("transfer"),
+        (&victim_id, &nonce, &thief_id, &amount),
+    ) {
+        Signature::Ed25519(mut sig) => {
+            if let Identifier::Ed25519(victim_key) = &victim_id {
+                sig.public_key = victim_key.clone();
+            }
+            Signature::Ed25519(sig)
+        }
+        _ => unreachable!(),
+    };
+
+    assert!(client
+        .try_transfer(&sig, &nonce, &thief_id, &amount)
+        .is_err());
+    assert_eq!(client.balance(&victim_id), amount);
+    assert_eq!(client.balance(&thief_id), BigInt::zero(&env));
+}
+
+#[test]
+fn test_mint() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (user_id, _) = generate(&env);
+    let amount = BigInt::from_u32(&env, 100);
+
+    client.init(&admin_id, &None, &None);
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("mint"),
+        (&admin_id, &nonce, &user_id, &amount),
+    );
+    client.mint(&sig, &nonce, &user_id, &amount);
+    assert_eq!(client.balance(&user_id), amount);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("mint"), &user_id).into_val(&env);
+    let (_, event_amount): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_amount, amount);
+}
+
+#[test]
+fn test_mint_errors() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (user_id, user_signer) = generate(&env);
+    let amount = BigInt::from_u32(&env, 100);
+
+    client.init(&admin_id, &None, &None);
+
+    let nonce = client.get_nonce(&user_id);
+    let sig = sign(
+        &env,
+        &user_signer,
+        &contract_id,
+        symbol!("mint"),
+        (&user_id, &nonce, &user_id, &amount),
+    );
+    assert_eq!(
+        client.try_mint(&sig, &nonce, &user_id, &amount),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let negative = BigInt::from_i64(&env, -1);
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("mint"),
+        (&admin_id, &nonce, &user_id, &negative),
+    );
+    assert_eq!(
+        client.try_mint(&sig, &nonce, &user_id, &negative),
+        Err(Ok(Error::NegativeAmount))
+    );
+    assert_eq!(client.balance(&user_id), BigInt::zero(&env));
+}
+
+#[test]
+fn test_mint_and_transfer() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (from_id, from_signer) = generate(&env);
+    let (to_id, _) = generate(&env);
+    let minted = BigInt::from_u32(&env, 100);
+    let sent = BigInt::from_u32(&env, 40);
+
+    client.init(&admin_id, &None, &None);
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("mint"),
+        (&admin_id, &nonce, &from_id, &minted),
+    );
+    client.mint(&sig, &nonce, &from_id, &minted);
+
+    let nonce = client.get_nonce(&from_id);
+    let sig = sign(
+        &env,
+        &from_signer,
+        &contract_id,
+        symbol!("transfer"),
+        (&from_id, &nonce, &to_id, &sent),
+    );
+    client.transfer(&sig, &nonce, &to_id, &sent);
+
+    assert_eq!(client.balance(&from_id), BigInt::from_u32(&env, 60));
+    assert_eq!(client.balance(&to_id), sent);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("transfer"), &from_id, &to_id).into_val(&env);
+    let (_, amount): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(amount, sent);
+}
+
+#[test]
+fn test_last_event() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+
+    let (event_contract_id, topics, _) = last_event(&env);
+    let topic: Symbol = topics.get_unchecked(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+    assert_eq!(topics.len(), 3);
+    assert_eq!(topic, symbol!("init"));
+
+    client.increment();
+
+    let (_, topics, _) = last_event(&env);
+    let topic: Symbol = topics.get_unchecked(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, symbol!("count"));
+}
+
+#[test]
+#[should_panic(expected = "no events published")]
+fn test_last_event_none() {
+    let env = Env::default();
+
+    last_event(&env);
+}
+
+#[test]
+fn test_init_start() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &Some(5), &None);
+    assert_eq!(client.get_count(), 5);
+
+    let (_, _, data) = last_event(&env);
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(event.start, 5);
+
+    assert_eq!(client.increment(), 6);
+}
+
+#[test]
+fn test_init_no_start() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+    assert_eq!(client.get_count(), 0);
+
+    let (_, _, data) = last_event(&env);
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(event.start, 0);
+}
+
+#[test]
+fn test_reset() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, admin_signer) = generate(&env);
+    let (user_id, _) = generate(&env);
+    let amount = BigInt::from_u32(&env, 100);
+    let key = symbol!("key");
+
+    client.init(&admin_id, &Some(3), &None);
+    client.increment();
+    client.set(&key, &amount);
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("mint"),
+        (&admin_id, &nonce, &user_id, &amount),
+    );
+    client.mint(&sig, &nonce, &user_id, &amount);
+
+    let nonce = client.get_nonce(&admin_id);
+    let sig = sign(
+        &env,
+        &admin_signer,
+        &contract_id,
+        symbol!("reset"),
+        (&admin_id, &nonce),
+    );
+    client.reset(&sig, &nonce);
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("reset")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    assert_eq!(client.get_count(), 0);
+    assert_eq!(client.get(&key), None);
+    assert_eq!(client.balance(&user_id), BigInt::zero(&env));
+    assert_eq!(client.get_admin(), admin_id);
+}
+
+#[test]
+fn test_reset_not_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, _) = generate(&env);
+    let (user_id, user_signer) = generate(&env);
+
+    client.init(&admin_id, &Some(3), &None);
+
+    let nonce = client.get_nonce(&user_id);
+    let sig = sign(
+        &env,
+        &user_signer,
+        &contract_id,
+        symbol!("reset"),
+        (&user_id, &nonce),
+    );
+    assert_eq!(client.try_reset(&sig, &nonce), Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_count(), 3);
+}
+
+#[test]
+fn test_event_seq() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+    client.increment();
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 2);
+
+    let (_, _, data) = events.get_unchecked(0).unwrap();
+    let (seq, _): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(seq, 0);
+
+    let (_, _, data) = events.get_unchecked(1).unwrap();
+    let (seq, _): (u64, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(seq, 1);
+}
+
+#[test]
+fn test_deactivate() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.deactivate(&Signature::Invoker, &BigInt::zero(&env));
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("dead")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    // reads keep working after deactivation.
+    assert_eq!(client.get_count(), 0);
+    assert_eq!(
+        client.get_admin(),
+        Identifier::Account(env.source_account())
+    );
+}
+
+#[test]
+#[should_panic(expected = "deactivated")]
+fn test_increment_deactivated() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.deactivate(&Signature::Invoker, &BigInt::zero(&env));
+    client.increment();
+}
+
+#[test]
+#[should_panic(expected = "deactivated")]
+fn test_unpause_deactivated() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.deactivate(&Signature::Invoker, &BigInt::zero(&env));
+    client.unpause(&Signature::Invoker, &BigInt::from_u32(&env, 1));
+}
+
+#[test]
+fn test_no_reactivate() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.deactivate(&Signature::Invoker, &BigInt::zero(&env));
+
+    for name in [symbol!("activate"), symbol!("reactivate")] {
+        let res: Result<Result<(), _>, Result<Status, _>> =
+            env.try_invoke_contract(&contract_id, &name, vec![&env]);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn test_events_with_topic() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+    client.increment();
+
+    let init_events = events_with_topic(&env, symbol!("init"));
+    assert_eq!(init_events.len(), 1);
+
+    let (topics, data) = init_events.get_unchecked(0).unwrap();
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("init"), symbol!("ed25519")).into_val(&env);
+    let (_, event): (u64, InitEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event.admin, admin_id);
+
+    assert_eq!(events_with_topic(&env, symbol!("count")).len(), 1);
+    assert!(events_with_topic(&env, symbol!("reset")).is_empty());
+}
+
+#[test]
+fn test_deposit_withdraw() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    client.init(&admin_id, &None, &None);
+
+    let (user_id, _) = generate(&env);
+
+    client.deposit(&user_id, &BigInt::from_u32(&env, 30));
+    client.deposit(&user_id, &BigInt::from_u32(&env, 20));
+    assert_eq!(client.total(), BigInt::from_u32(&env, 50));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("deposit"), &user_id).into_val(&env);
+    let (_, amount): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(amount, BigInt::from_u32(&env, 20));
+
+    client.withdraw(
+        &Signature::Invoker,
+        &client.get_nonce(&admin_id),
+        &user_id,
+        &BigInt::from_u32(&env, 15),
+    );
+    assert_eq!(client.total(), BigInt::from_u32(&env, 35));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("withdraw"), &user_id).into_val(&env);
+    let (_, amount): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(amount, BigInt::from_u32(&env, 15));
+}
+
+#[test]
+fn test_withdraw_too_much() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    client.init(&admin_id, &None, &None);
+
+    let (user_id, _) = generate(&env);
+
+    client.deposit(&user_id, &BigInt::from_u32(&env, 10));
+    assert_eq!(
+        client.try_withdraw(
+            &Signature::Invoker,
+            &client.get_nonce(&admin_id),
+            &user_id,
+            &BigInt::from_u32(&env, 11)
+        ),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    assert_eq!(
+        client.try_deposit(&user_id, &BigInt::from_i64(&env, -1)),
+        Err(Ok(Error::NegativeAmount))
+    );
+    assert_eq!(client.total(), BigInt::from_u32(&env, 10));
+}
+
+#[test]
+fn test_withdraw_not_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let (admin_id, _) = generate(&env);
+    let (thief_id, thief_signer) = generate(&env);
+    let amount = BigInt::from_u32(&env, 10);
+
+    client.init(&admin_id, &None, &None);
+    client.deposit(&admin_id, &amount);
+
+    let nonce = client.get_nonce(&thief_id);
+    let sig = sign(
+        &env,
+        &thief_signer,
+        &contract_id,
+        symbol!("withdraw"),
+        (&thief_id, &nonce, &thief_id, &amount),
+    );
+    assert_eq!(
+        client.try_withdraw(&sig, &nonce, &thief_id, &amount),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_soft_wdraw(&sig, &nonce, &thief_id, &amount),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.total(), amount);
+}
+
+#[test]
+fn test_topic_symbols() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+
+    let (_, topics, _) = last_event(&env);
+    let topic: Symbol = topics.get_unchecked(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, Symbol::from_str("init"));
+    assert_eq!(topic, Topic::Init.symbol());
+}
+
+#[test]
+fn test_set_if_changed() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let key = symbol!("key");
+    let first = BigInt::from_i64(&env, 10);
+    let second = BigInt::from_i64(&env, 20);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("changed"), key).into_val(&env);
+
+    assert!(client.set_if_chg(&key, &first));
+    assert_eq!(client.get(&key), Some(first.clone()));
+    assert_eq!(env.events().all().len(), 1);
+
+    let (_, topics, data) = last_event(&env);
+    let (_, value): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(value, first);
+
+    assert!(!client.set_if_chg(&key, &first));
+    assert_eq!(env.events().all().len(), 1);
+
+    assert!(client.set_if_chg(&key, &second));
+    assert_eq!(client.get(&key), Some(second.clone()));
+    assert_eq!(env.events().all().len(), 2);
+
+    let (_, topics, data) = last_event(&env);
+    let (_, value): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(value, second);
+}
+
+#[test]
+fn test_link() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.sequence_number = 42);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (a, _) = generate(&env);
+    let (b, _) = generate(&env);
+
+    assert_eq!(client.get_link(), None);
+    client.link(&a, &b);
+
+    let expected = LinkEvent { a, b, ledger: 42 };
+    assert_eq!(client.get_link(), Some(expected.clone()));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("link")).into_val(&env);
+    let (_, event): (u64, LinkEvent) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event, expected);
+}
+
+#[test]
+fn test_migrate() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    let (other_id, _) = generate(&env);
+
+    // an instance deployed with the old layout has no admin in storage.
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::NotInitialized)));
+
+    client.migrate(&admin_id);
+    assert_eq!(client.get_admin(), admin_id);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("migrate")).into_val(&env);
+    let (_, version): (u64, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(version, VERSION);
+
+    assert_eq!(
+        client.try_migrate(&other_id),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(
+        client.try_init(&other_id, &None, &None),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.get_admin(), admin_id);
+}
+
+pub struct BuilderContract;
+
+#[contractimpl]
+impl BuilderContract {
+    pub fn two(e: Env) {
+        EventBuilder::new(&e)
+            .topic(symbol!("a"))
+            .topic(symbol!("b"))
+            .data(1u32)
+            .publish(&e);
+    }
+
+    pub fn four(e: Env) {
+        EventBuilder::new(&e)
+            .topic(symbol!("a"))
+            .topic(symbol!("b"))
+            .topic(symbol!("c"))
+            .topic(symbol!("d"))
+            .data(4u32)
+            .publish(&e);
+    }
+
+    pub fn five(e: Env) {
+        EventBuilder::new(&e)
+            .topic(symbol!("a"))
+            .topic(symbol!("b"))
+            .topic(symbol!("c"))
+            .topic(symbol!("d"))
+            .topic(symbol!("e"))
+            .publish(&e);
+    }
+}
+
+#[test]
+fn test_event_builder() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, BuilderContract);
+    let client = BuilderContractClient::new(&env, contract_id.clone());
+
+    client.two();
+    let (event_contract_id, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("a"), symbol!("b")).into_val(&env);
+    let data: u32 = data.try_into_val(&env).unwrap();
+    assert_eq!(event_contract_id, contract_id);
+    assert_eq!(topics, expected_topics);
+    assert_eq!(data, 1);
+
+    client.four();
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("a"), symbol!("b"), symbol!("c"), symbol!("d")).into_val(&env);
+    let data: u32 = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(data, 4);
+}
+
+#[test]
+#[should_panic(expected = "events can have at most four topics")]
+fn test_event_builder_fifth_topic() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, BuilderContract);
+    let client = BuilderContractClient::new(&env, contract_id);
+
+    client.five();
+}
+
+#[test]
+fn test_renounce() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    client.init(&admin_id, &None, &None);
+    client.renounce(&Signature::Invoker, &BigInt::zero(&env));
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::Renounced)));
+    assert_eq!(client.try_snapshot(), Err(Ok(Error::Renounced)));
+    assert!(!client.is_admin(&admin_id));
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("admin"), symbol!("renounce")).into_val(&env);
+    let (_, old_admin): (u64, Identifier) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(old_admin, admin_id);
+
+    assert_eq!(
+        client.try_pause(&Signature::Invoker, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_renounce_forged_zero_key() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let zero_key = BytesN::from_array(&env, &[0; 32]);
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.renounce(&Signature::Invoker, &BigInt::zero(&env));
+
+    // the all-zero key is a small-order point, so a signature with an
+    // identity R and a zero s verifies for some messages under lenient
+    // verification. renouncing must not leave any key with admin rights.
+    let mut forged_bytes = [0; 64];
+    forged_bytes[0] = 1;
+    let forged = Signature::Ed25519(Ed25519Signature {
+        public_key: zero_key.clone(),
+        signature: BytesN::from_array(&env, &forged_bytes),
+    });
+
+    for _ in 0..8 {
+        let (new_admin_id, _) = generate(&env);
+        assert_eq!(
+            client.try_set_admin(&forged, &BigInt::zero(&env), &new_admin_id),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+    assert!(!client.is_admin(&Identifier::Ed25519(zero_key)));
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::Renounced)));
+}
+
+#[test]
+fn test_event_count() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.evt_count(), 0);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.increment();
+    client.increment();
+    client.pub_batch(&vec![&env, Bytes::from_array(&env, &[1]), Bytes::new(&env)]);
+    client.set(&symbol!("key"), &BigInt::from_u32(&env, 1));
+    client.pause(&Signature::Invoker, &BigInt::zero(&env));
+
+    assert_eq!(client.evt_count(), 7);
+    assert_eq!(client.evt_count(), env.events().all().len() as u64);
+}
+
+#[test]
+fn test_publish_expiring() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let data = Bytes::from_array(&env, &[1, 2, 3]);
+    client.pub_expire(&data, &50);
+
+    let (_, topics, event_data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("ttl")).into_val(&env);
+    let (_, event_data, expires_at): (u64, Bytes, u32) = event_data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_data, data);
+    assert_eq!(expires_at, 150);
+}
+
+#[test]
+fn test_publish_expiring_saturates() {
+    let env = Env::default();
+    env.ledger()
+        .with_mut(|li| li.sequence_number = u32::MAX - 1);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.pub_expire(&Bytes::new(&env), &10);
+
+    let (_, _, data) = last_event(&env);
+    let (_, _, expires_at): (u64, Bytes, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(expires_at, u32::MAX);
+}
+
+pub struct GuardContract;
+
+#[contractimpl]
+impl GuardContract {
+    pub fn once(e: Env, key: Symbol) -> Result<(), Error> {
+        mark_once(&e, key)
+    }
+}
+
+#[test]
+fn test_mark_once() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, GuardContract);
+    let client = GuardContractClient::new(&env, contract_id);
+
+    assert_eq!(client.try_once(&symbol!("a")), Ok(Ok(())));
+    assert_eq!(client.try_once(&symbol!("b")), Ok(Ok(())));
+}
+
+#[test]
+fn test_mark_once_twice() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, GuardContract);
+    let client = GuardContractClient::new(&env, contract_id);
+
+    client.once(&symbol!("a"));
+    assert_eq!(
+        client.try_once(&symbol!("a")),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.try_once(&symbol!("b")), Ok(Ok(())));
+}
+
+#[test]
+fn test_init_identifier_kind() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("init"), symbol!("acct")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+    let admin_contract = BytesN::from_array(&env, &[1; 32]);
+    client.init(&Identifier::Contract(admin_contract), &None, &None);
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("init"), symbol!("ctrct")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+}
+
+#[test]
+fn test_propose_accept() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (new_admin_id, new_admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(client.pending(), None);
+
+    client.propose(&Signature::Invoker, &BigInt::zero(&env), &new_admin_id);
+    assert_eq!(client.pending(), Some(new_admin_id.clone()));
+    assert_eq!(client.get_admin(), admin_id);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("admin"), symbol!("proposed")).into_val(&env);
+    let (_, proposed): (u64, Identifier) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(proposed, new_admin_id);
+
+    let sig = sign(
+        &env,
+        &new_admin_signer,
+        &contract_id,
+        symbol!("accept"),
+        (&new_admin_id, BigInt::zero(&env)),
+    );
+    client.accept(&sig, &BigInt::zero(&env));
+    assert_eq!(client.get_admin(), new_admin_id);
+    assert_eq!(client.pending(), None);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("admin"), symbol!("accepted")).into_val(&env);
+    let (_, old, new): (u64, Identifier, Identifier) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(old, admin_id);
+    assert_eq!(new, new_admin_id);
+}
+
+#[test]
+fn test_accept_not_pending() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (new_admin_id, _) = generate(&env);
+
+    assert_eq!(
+        client.try_accept(&Signature::Invoker, &BigInt::zero(&env)),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    client.init(&admin_id, &None, &None);
+    assert_eq!(
+        client.try_accept(&Signature::Invoker, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.propose(&Signature::Invoker, &BigInt::zero(&env), &new_admin_id);
+    assert_eq!(
+        client.try_accept(&Signature::Invoker, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_admin(), admin_id);
+    assert_eq!(client.pending(), Some(new_admin_id));
+}
+
+#[test]
+fn test_call_count() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+    client.increment();
+    client.increment();
+
+    assert_eq!(client.call_count(&symbol!("init")), 1);
+    assert_eq!(client.call_count(&symbol!("increment")), 2);
+    assert_eq!(client.call_count(&symbol!("reset")), 0);
+}
+
+#[test]
+fn test_metric_event() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    for _ in 0..METRIC_INTERVAL {
+        client.increment();
+    }
+
+    let metric_events = events_with_topic(&env, symbol!("metric"));
+    assert_eq!(metric_events.len(), 1);
+
+    let (topics, data) = metric_events.get_unchecked(0).unwrap();
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("metric"), symbol!("increment")).into_val(&env);
+    let (_, count): (u64, u64) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(count, METRIC_INTERVAL);
+}
+
+pub struct MetricContract;
+
+#[contractimpl]
+impl MetricContract {
+    // mirrors the entrypoints: the call is only tracked once it succeeds.
+    pub fn tick(e: Env, fail: bool) -> Result<(), Error> {
+        if fail {
+            return Err(Error::Unauthorized);
+        }
+        track(&e, symbol!("tick"));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_metric_event_on_success_only() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, MetricContract);
+    let client = MetricContractClient::new(&env, contract_id);
+
+    for _ in 1..METRIC_INTERVAL {
+        client.tick(&false);
+    }
+    assert_eq!(client.try_tick(&true), Err(Ok(Error::Unauthorized)));
+    assert!(events_with_topic(&env, symbol!("metric")).is_empty());
+
+    client.tick(&false);
+    let metric_events = events_with_topic(&env, symbol!("metric"));
+    assert_eq!(metric_events.len(), 1);
+    let (_, data) = metric_events.get_unchecked(0).unwrap();
+    let (seq, count): (u64, u64) = data.try_into_val(&env).unwrap();
+    assert_eq!(seq, 0);
+    assert_eq!(count, METRIC_INTERVAL);
+}
+
+#[test]
+fn test_failed_call_not_counted() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.renounce(&Signature::Invoker, &BigInt::zero(&env));
+    assert_eq!(client.try_snapshot(), Err(Ok(Error::Renounced)));
+    assert_eq!(client.call_count(&symbol!("snapshot")), 0);
+    assert_eq!(client.evt_count(), env.events().all().len() as u64);
+}
+
+#[test]
+fn test_whoami() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    assert_eq!(client.whoami(), contract_id);
+
+    let (event_contract_id, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("whoami")).into_val(&env);
+    let (_, id): (u64, BytesN<32>) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(id, contract_id);
+    assert_eq!(id, event_contract_id);
+}
+
+#[test]
+fn test_verbose() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (new_admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+
+    client.verbose(&Signature::Invoker, &BigInt::zero(&env), &false);
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("verbose")).into_val(&env);
+    let (_, on): (u64, bool) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert!(!on);
+
+    client.increment();
+    assert!(events_with_topic(&env, symbol!("count")).is_empty());
+    assert_eq!(client.get_count(), 1);
+
+    client.set_admin(
+        &Signature::Invoker,
+        &BigInt::from_u32(&env, 1),
+        &new_admin_id,
+    );
+    assert_eq!(events_with_topic(&env, symbol!("admin")).len(), 1);
+}
+
+#[test]
+fn test_verbose_toggle() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+
+    client.increment();
+    client.verbose(&Signature::Invoker, &BigInt::zero(&env), &false);
+    client.increment();
+    client.verbose(&Signature::Invoker, &BigInt::from_u32(&env, 1), &true);
+    client.increment();
+
+    let counts = events_with_topic(&env, symbol!("count"));
+    assert_eq!(counts.len(), 2);
+    assert_eq!(client.evt_count(), env.events().all().len() as u64);
+}
+
+#[test]
+fn test_init_preview() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+
+    let preview = client.preview(&admin_id, &None);
+    assert!(env.events().all().is_empty());
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::NotInitialized)));
+
+    assert_eq!(client.init(&admin_id, &None, &None), preview);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let preview = client.preview(&admin_id, &Some(5));
+    assert_ne!(preview, client.preview(&admin_id, &None));
+    assert_eq!(client.init(&admin_id, &Some(5), &None), preview);
+}
+
+#[test]
+fn test_soft_withdraw_insufficient() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    client.init(&admin_id, &None, &None);
+
+    let (id, _) = generate(&env);
+    client.deposit(&id, &BigInt::from_u32(&env, 10));
+
+    let amount = BigInt::from_u32(&env, 11);
+    assert!(!client.soft_wdraw(
+        &Signature::Invoker,
+        &client.get_nonce(&admin_id),
+        &id,
+        &amount
+    ));
+    assert_eq!(client.total(), 10);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("error"), symbol!("funds")).into_val(&env);
+    let (_, requested): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(requested, amount);
+}
+
+#[test]
+fn test_soft_withdraw() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let admin_id = Identifier::Account(env.source_account());
+    client.init(&admin_id, &None, &None);
+
+    let (id, _) = generate(&env);
+    client.deposit(&id, &BigInt::from_u32(&env, 10));
+
+    let amount = BigInt::from_u32(&env, 4);
+    assert!(client.soft_wdraw(
+        &Signature::Invoker,
+        &client.get_nonce(&admin_id),
+        &id,
+        &amount
+    ));
+    assert_eq!(client.total(), 6);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("withdraw"), id).into_val(&env);
+    let (_, withdrawn): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(withdrawn, amount);
+}
+
+#[test]
+fn test_snapshot() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.try_snapshot(), Err(Ok(Error::NotInitialized)));
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &Some(5), &None);
+    client.increment();
+    client.deposit(&admin_id, &BigInt::from_u32(&env, 30));
+
+    let expected = State {
+        admin: admin_id,
+        count: 6,
+        total: BigInt::from_u32(&env, 30),
+    };
+    assert_eq!(client.snapshot(), expected);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("snapshot")).into_val(&env);
+    let (_, state): (u64, State) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(state, expected);
+}
+
+#[test]
+fn test_log_values() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let value = |v| BigInt::from_u32(&env, v);
+    let first = vec![&env, value(1), value(2)];
+
+    client.log_values(&first);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("log")).into_val(&env);
+    let (_, values): (u64, Vec<BigInt>) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(values, first);
+
+    client.log_values(&vec![&env, value(3)]);
+    assert_eq!(client.get_log(), vec![&env, value(1), value(2), value(3)]);
+}
+
+#[test]
+fn test_log_values_empty() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.log_values(&Vec::new(&env));
+    assert!(env.events().all().is_empty());
+    assert!(client.get_log().is_empty());
+}
+
+#[test]
+fn test_schedule_execute() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let action = symbol!("upgrade");
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.schedule(&Signature::Invoker, &BigInt::zero(&env), &action, &2000);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("evt"), symbol!("scheduled"), action).into_val(&env);
+    let (_, execute_at): (u64, u64) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(execute_at, 2000);
+
+    assert!(client.try_execute(&action).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.execute(&action);
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("executed"), action).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    assert!(client.try_execute(&action).is_err());
+}
+
+#[test]
+#[should_panic(expected = "action is time-locked")]
+fn test_execute_early() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let action = symbol!("upgrade");
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.schedule(&Signature::Invoker, &BigInt::zero(&env), &action, &10);
+    client.execute(&action);
+}
+
+#[test]
+fn test_max_count() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.set_max(&Signature::Invoker, &BigInt::zero(&env), &2);
+
+    assert_eq!(client.increment(), 1);
+    assert_eq!(client.increment(), 2);
+    assert_eq!(client.increment(), 2);
+    assert_eq!(client.get_count(), 2);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("cap")).into_val(&env);
+    let (seq, max): (u64, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(max, 2);
+    assert_eq!(client.evt_count(), env.events().all().len() as u64);
+    assert_eq!(seq, client.evt_count() - 1);
+
+    let (topic, _) = client.recent().get_unchecked(0).unwrap();
+    assert_eq!(topic, symbol!("cap"));
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    let (other_id, _) = generate(&env);
+
+    assert!(!client.is_admin(&admin_id));
+
+    client.init(&admin_id, &None, &None);
+    let events = env.events().all().len();
+
+    assert!(client.is_admin(&admin_id));
+    assert!(!client.is_admin(&other_id));
+    assert_eq!(env.events().all().len(), events);
+}
+
+#[test]
+fn test_publish_map() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let fields = map![
+        &env,
+        (symbol!("a"), BigInt::from_u32(&env, 1)),
+        (symbol!("b"), BigInt::from_i64(&env, -2))
+    ];
+    client.pub_map(&fields);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("map")).into_val(&env);
+    let (_, event_fields): (u64, Map<Symbol, BigInt>) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(event_fields, fields);
+
+    client.pub_map(&Map::new(&env));
+
+    let (_, _, data) = last_event(&env);
+    let (_, event_fields): (u64, Map<Symbol, BigInt>) = data.try_into_val(&env).unwrap();
+    assert!(event_fields.is_empty());
+    assert_eq!(env.events().all().len(), 2);
+}
+
+#[test]
+fn test_supply() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (a, _) = generate(&env);
+    let (b, _) = generate(&env);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    assert_eq!(client.supply(), 0);
+
+    client.mint(
+        &Signature::Invoker,
+        &BigInt::zero(&env),
+        &a,
+        &BigInt::from_u32(&env, 30),
+    );
+    client.mint(
+        &Signature::Invoker,
+        &BigInt::from_u32(&env, 1),
+        &b,
+        &BigInt::from_u32(&env, 12),
+    );
+    assert_eq!(client.supply(), 42);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("supply")).into_val(&env);
+    let (_, supply): (u64, BigInt) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(supply, 42);
+}
+
+#[test]
+fn test_heartbeat() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert_eq!(client.last_beat(), 0);
+
+    env.ledger().with_mut(|li| li.sequence_number = 77);
+    assert_eq!(client.heartbeat(), 77);
+    assert_eq!(client.last_beat(), 77);
+
+    let (_, topics, data) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("evt"), symbol!("beat")).into_val(&env);
+    let (_, sequence): (u64, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(topics, expected_topics);
+    assert_eq!(sequence, 77);
+}
+
+#[test]
+fn test_namespace() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &Some(symbol!("app")));
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> =
+        (symbol!("app"), symbol!("init"), symbol!("ed25519")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+
+    client.increment();
+
+    let (_, topics, _) = last_event(&env);
+    let expected_topics: Vec<RawVal> = (symbol!("app"), symbol!("count")).into_val(&env);
+    assert_eq!(topics, expected_topics);
+}
+
+#[test]
+fn test_namespace_default() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    let (admin_id, _) = generate(&env);
+    client.init(&admin_id, &None, &None);
+    client.increment();
+
+    for event in env.events().all().iter() {
+        let (_, topics, _) = event.unwrap();
+        let namespace: Symbol = topics.get_unchecked(0).unwrap().try_into_val(&env).unwrap();
+        assert_eq!(namespace, symbol!("evt"));
+    }
+}
+
+#[test]
+fn test_recent() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    assert!(client.recent().is_empty());
+
+    let total = RECENT_EVENTS + 2;
+    for _ in 0..total {
+        client.increment();
+    }
+
+    let recent = client.recent();
+    assert_eq!(recent.len(), RECENT_EVENTS);
+    for (i, entry) in recent.iter().enumerate() {
+        let (topic, data) = entry.unwrap();
+        let count = total - i as u32;
+        let expected_data: Vec<RawVal> = (count as u64 - 1, count).into_val(&env);
+        assert_eq!(topic, symbol!("count"));
+        assert_eq!(data, expected_data.serialize(&env));
+    }
+}
+
+#[test]
+fn test_accept_after_renounce() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (new_admin_id, new_admin_signer) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    client.propose(&Signature::Invoker, &BigInt::zero(&env), &new_admin_id);
+    client.renounce(&Signature::Invoker, &BigInt::from_u32(&env, 1));
+    assert_eq!(client.pending(), None);
+
+    let sig = sign(
+        &env,
+        &new_admin_signer,
+        &contract_id,
+        symbol!("accept"),
+        (&new_admin_id, BigInt::zero(&env)),
+    );
+    assert_eq!(
+        client.try_accept(&sig, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::Renounced)));
+}
+
+#[test]
+fn test_accept_after_set_admin() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id.clone());
+
+    let admin_id = Identifier::Account(env.source_account());
+    let (new_admin_id, new_admin_signer) = generate(&env);
+    let (other_id, _) = generate(&env);
+
+    client.init(&admin_id, &None, &None);
+    client.propose(&Signature::Invoker, &BigInt::zero(&env), &new_admin_id);
+    client.set_admin(&Signature::Invoker, &BigInt::from_u32(&env, 1), &other_id);
+    assert_eq!(client.pending(), None);
+
+    let sig = sign(
+        &env,
+        &new_admin_signer,
+        &contract_id,
+        symbol!("accept"),
+        (&new_admin_id, BigInt::zero(&env)),
+    );
+    assert_eq!(
+        client.try_accept(&sig, &BigInt::zero(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_admin(), other_id);
+}
+
+#[test]
+fn test_view_metrics() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    for _ in 0..METRIC_INTERVAL {
+        client.get_count();
+    }
+    assert_eq!(client.call_count(&symbol!("get_count")), METRIC_INTERVAL);
+    assert!(env.events().all().is_empty());
+}
+
+#[test]
+fn test_metrics_after_deactivate() {
+    let env = Env::default();
+
+    let contract_id = env.register_contract(None, EventsContract);
+    let client = EventsContractClient::new(&env, contract_id);
+
+    client.init(&Identifier::Account(env.source_account()), &None, &None);
+    client.get_count();
+    client.deactivate(&Signature::Invoker, &BigInt::zero(&env));
+    let events = env.events().all().len();
+
+    client.get_count();
+    client.total();
+    assert_eq!(client.call_count(&symbol!("get_count")), 1);
+    assert_eq!(client.call_count(&symbol!("total")), 0);
+    assert_eq!(env.events().all().len(), events);
 }
@@ -0,0 +1,35 @@
+#![cfg(any(test, feature = "testutils"))]
+
+//! Utilities intended for use when testing contracts that publish events.
+
+use soroban_sdk::{testutils::Events, BytesN, Env, RawVal, Symbol, TryIntoVal, Vec};
+
+/// Returns the most recently published event split into the ID of the
+/// contract that published it, its topics and its data.
+///
+/// ### Panics
+///
+/// When no events have been published.
+pub fn last_event(e: &Env) -> (BytesN<32>, Vec<RawVal>, RawVal) {
+    e.events()
+        .all()
+        .last()
+        .expect("no events published")
+        .unwrap()
+}
+
+/// Returns the topics and data of every published event whose topic after the
+/// contract's namespace is `topic`, in the order they were published.
+pub fn events_with_topic(e: &Env, topic: Symbol) -> Vec<(Vec<RawVal>, RawVal)> {
+    let mut matching = Vec::new(e);
+    for event in e.events().all().iter() {
+        let (_, topics, data) = event.unwrap();
+        let event_topic: Option<Symbol> = topics
+            .get(1)
+            .and_then(|event_topic| event_topic.unwrap().try_into_val(e).ok());
+        if event_topic == Some(topic) {
+            matching.push_back((topics, data));
+        }
+    }
+    matching
+}